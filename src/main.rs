@@ -14,11 +14,11 @@ fn main() {
 // execute waits 5 seconds and returns ok
 struct SimpleWork {
     id: u64,
-    status: work::WorkStatus,
+    state: work::WorkState,
 }
 
 impl work::Work for SimpleWork {
-    fn execute(&self) -> Result<(), WorkError> {
+    fn execute(&self, _ctx: &work::WorkContext) -> Result<(), WorkError> {
         println!("SimpleWork {} executing", self.id);
         thread::sleep(std::time::Duration::from_secs(2));
         println!("SimpleWork {} complete", self.id);
@@ -26,7 +26,7 @@ impl work::Work for SimpleWork {
     }
 
     fn status(&self) -> work::WorkStatus {
-        self.status
+        work::WorkStatus::new(self.state)
     }
 }
 